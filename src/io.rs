@@ -1,6 +1,7 @@
 use iobuf::{RWIobuf, Iobuf};
 use os;
 use error::MioResult;
+use socket::{Inet, SockAddr, Dgram};
 
 pub enum NonBlock<T> {
     Ready(T),
@@ -27,18 +28,77 @@ pub trait IoHandle {
     fn desc(&self) -> &os::IoDesc;
 }
 
+pub trait AsRawFd {
+    fn as_raw_fd(&self) -> os::Fd;
+}
+
+pub trait IntoRawFd {
+    fn into_raw_fd(self) -> os::Fd;
+}
+
+pub trait FromRawFd {
+    unsafe fn from_raw_fd(fd: os::Fd) -> Self;
+}
+
 pub trait IoReader {
     fn read(&self, buf: &mut RWIobuf) -> MioResult<NonBlock<()>>;
+
+    /// Defaults to reading each buffer in turn via `read`; implementors
+    /// backed by an fd can override this with a single `readv(2)` call.
+    fn read_vectored(&self, bufs: &mut [RWIobuf]) -> MioResult<NonBlock<()>> {
+        for buf in bufs.iter_mut() {
+            match try!(self.read(buf)) {
+                Ready(()) => {},
+                WouldBlock => return Ok(WouldBlock)
+            }
+        }
+
+        Ok(Ready(()))
+    }
 }
 
 pub trait IoWriter {
     fn write<B: Iobuf>(&self, buf: &mut B) -> MioResult<NonBlock<()>>;
+
+    /// Defaults to writing each buffer in turn via `write`; implementors
+    /// backed by an fd can override this with a single `writev(2)` call.
+    fn write_vectored<B: Iobuf>(&self, bufs: &mut [B]) -> MioResult<NonBlock<()>> {
+        for buf in bufs.iter_mut() {
+            match try!(self.write(buf)) {
+                Ready(()) => {},
+                WouldBlock => return Ok(WouldBlock)
+            }
+        }
+
+        Ok(Ready(()))
+    }
 }
 
 pub trait IoAcceptor<T> {
     fn accept(&self) -> MioResult<NonBlock<T>>;
 }
 
+pub trait IoDatagram {
+    fn recv_from(&self, buf: &mut RWIobuf) -> MioResult<NonBlock<SockAddr>>;
+    fn send_to<B: Iobuf>(&self, buf: &mut B, addr: &SockAddr) -> MioResult<NonBlock<()>>;
+}
+
+pub enum Shutdown {
+    Read,
+    Write,
+    Both
+}
+
+/// Half-closes a connection without tearing down the whole `IoDesc`. The
+/// motivating consumer is a `TcpSocket`-backed `EchoClient` half-closing its
+/// write side after its last message while still draining inbound data --
+/// but `TcpSocket` isn't part of this tree, so `UdpSocket` is the only
+/// implementor here and `test/test_echo_server.rs` doesn't exercise this at
+/// all. Wire it up on `TcpSocket` once that type exists.
+pub trait IoShutdown {
+    fn shutdown(&self, how: Shutdown) -> MioResult<()>;
+}
+
 pub fn pipe() -> MioResult<(PipeReader, PipeWriter)> {
     let (rd, wr) = try!(os::pipe());
     Ok((PipeReader { desc: rd }, PipeWriter { desc: wr }))
@@ -48,32 +108,142 @@ pub struct PipeReader {
     desc: os::IoDesc
 }
 
+impl PipeReader {
+    pub fn try_clone(&self) -> MioResult<PipeReader> {
+        Ok(PipeReader { desc: try!(os::dup(&self.desc)) })
+    }
+}
+
 impl IoHandle for PipeReader {
     fn desc(&self) -> &os::IoDesc {
         &self.desc
     }
 }
 
+impl AsRawFd for PipeReader {
+    fn as_raw_fd(&self) -> os::Fd {
+        self.desc.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for PipeReader {
+    fn into_raw_fd(self) -> os::Fd {
+        self.desc.into_raw_fd()
+    }
+}
+
+impl FromRawFd for PipeReader {
+    unsafe fn from_raw_fd(fd: os::Fd) -> PipeReader {
+        PipeReader { desc: os::IoDesc::from_raw_fd(fd) }
+    }
+}
+
 pub struct PipeWriter {
     desc: os::IoDesc
 }
 
+impl PipeWriter {
+    pub fn try_clone(&self) -> MioResult<PipeWriter> {
+        Ok(PipeWriter { desc: try!(os::dup(&self.desc)) })
+    }
+}
+
 impl IoHandle for PipeWriter {
     fn desc(&self) -> &os::IoDesc {
         &self.desc
     }
 }
 
+impl AsRawFd for PipeWriter {
+    fn as_raw_fd(&self) -> os::Fd {
+        self.desc.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for PipeWriter {
+    fn into_raw_fd(self) -> os::Fd {
+        self.desc.into_raw_fd()
+    }
+}
+
+impl FromRawFd for PipeWriter {
+    unsafe fn from_raw_fd(fd: os::Fd) -> PipeWriter {
+        PipeWriter { desc: os::IoDesc::from_raw_fd(fd) }
+    }
+}
+
 impl IoReader for PipeReader {
     fn read(&self, buf: &mut RWIobuf) -> MioResult<NonBlock<()>> {
         read(self, buf)
     }
+
+    fn read_vectored(&self, bufs: &mut [RWIobuf]) -> MioResult<NonBlock<()>> {
+        read_vectored(self, bufs)
+    }
 }
 
 impl IoWriter for PipeWriter {
     fn write<B: Iobuf>(&self, buf: &mut B) -> MioResult<NonBlock<()>> {
         write(self, buf)
     }
+
+    fn write_vectored<B: Iobuf>(&self, bufs: &mut [B]) -> MioResult<NonBlock<()>> {
+        write_vectored(self, bufs)
+    }
+}
+
+pub struct UdpSocket {
+    desc: os::IoDesc
+}
+
+impl UdpSocket {
+    pub fn v4() -> MioResult<UdpSocket> {
+        Ok(UdpSocket { desc: try!(os::socket(Inet, Dgram)) })
+    }
+
+    pub fn try_clone(&self) -> MioResult<UdpSocket> {
+        Ok(UdpSocket { desc: try!(os::dup(&self.desc)) })
+    }
+}
+
+impl IoHandle for UdpSocket {
+    fn desc(&self) -> &os::IoDesc {
+        &self.desc
+    }
+}
+
+impl AsRawFd for UdpSocket {
+    fn as_raw_fd(&self) -> os::Fd {
+        self.desc.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for UdpSocket {
+    fn into_raw_fd(self) -> os::Fd {
+        self.desc.into_raw_fd()
+    }
+}
+
+impl FromRawFd for UdpSocket {
+    unsafe fn from_raw_fd(fd: os::Fd) -> UdpSocket {
+        UdpSocket { desc: os::IoDesc::from_raw_fd(fd) }
+    }
+}
+
+impl IoDatagram for UdpSocket {
+    fn recv_from(&self, buf: &mut RWIobuf) -> MioResult<NonBlock<SockAddr>> {
+        recv_from(self, buf)
+    }
+
+    fn send_to<B: Iobuf>(&self, buf: &mut B, addr: &SockAddr) -> MioResult<NonBlock<()>> {
+        send_to(self, buf, addr)
+    }
+}
+
+impl IoShutdown for UdpSocket {
+    fn shutdown(&self, how: Shutdown) -> MioResult<()> {
+        shutdown(self, how)
+    }
 }
 
 pub fn read<I: IoHandle>(io: &I, buf: &mut RWIobuf) -> MioResult<NonBlock<()>> {
@@ -124,3 +294,79 @@ pub fn write<O: IoHandle, B: Iobuf>(io: &O, buf: &mut B) -> MioResult<NonBlock<(
 
     Ok(Ready(()))
 }
+
+pub fn read_vectored<I: IoHandle>(io: &I, bufs: &mut [RWIobuf]) -> MioResult<NonBlock<()>> {
+    let mut first_iter = true;
+
+    while bufs.iter().any(|b| !b.is_empty()) {
+        match os::readv(io.desc(), bufs) {
+            Ok(()) => {
+                first_iter = false;
+            }
+            Err(e) => {
+                if e.is_would_block() {
+                    return Ok(WouldBlock);
+                }
+
+                if e.is_eof() {
+                    if first_iter {
+                        return Err(e);
+                    }
+
+                    return Ok(Ready(()));
+                }
+
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(Ready(()))
+}
+
+pub fn write_vectored<O: IoHandle, B: Iobuf>(io: &O, bufs: &mut [B]) -> MioResult<NonBlock<()>> {
+    while bufs.iter().any(|b| !b.is_empty()) {
+        match os::writev(io.desc(), bufs) {
+            Ok(()) => {},
+            Err(e) => {
+                if e.is_would_block() {
+                    return Ok(WouldBlock);
+                }
+
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(Ready(()))
+}
+
+pub fn recv_from<I: IoHandle>(io: &I, buf: &mut RWIobuf) -> MioResult<NonBlock<SockAddr>> {
+    match os::recvfrom(io.desc(), buf) {
+        Ok(addr) => Ok(Ready(addr)),
+        Err(e) => {
+            if e.is_would_block() {
+                return Ok(WouldBlock);
+            }
+
+            Err(e)
+        }
+    }
+}
+
+pub fn send_to<O: IoHandle, B: Iobuf>(io: &O, buf: &mut B, addr: &SockAddr) -> MioResult<NonBlock<()>> {
+    match os::sendto(io.desc(), buf, addr) {
+        Ok(()) => Ok(Ready(())),
+        Err(e) => {
+            if e.is_would_block() {
+                return Ok(WouldBlock);
+            }
+
+            Err(e)
+        }
+    }
+}
+
+pub fn shutdown<I: IoHandle>(io: &I, how: Shutdown) -> MioResult<()> {
+    os::shutdown(io.desc(), how)
+}
@@ -1,16 +1,21 @@
 use iobuf::{Iobuf, RWIobuf};
 use std::mem;
 use error::{MioResult, MioError};
-use socket::{AddressFamily, Inet, Inet6, SockAddr, InetAddr, IpV4Addr};
+use socket::{AddressFamily, Inet, Inet6, SockAddr, InetAddr, IpV4Addr, IpV6Addr, SockType, Stream, Dgram};
+use io::{Shutdown, Read, Write, Both};
 
 mod nix {
     pub use nix::c_int;
-    pub use nix::fcntl::{Fd, O_NONBLOCK, O_CLOEXEC};
+    pub use nix::fcntl::{Fd, O_NONBLOCK, O_CLOEXEC, fcntl};
+    pub use nix::fcntl::FcntlArg::F_DUPFD_CLOEXEC;
     pub use nix::errno::EINPROGRESS;
     pub use nix::sys::socket::*;
     pub use nix::unistd::*;
 }
 
+/// The OS's native representation of an fd.
+pub type Fd = nix::Fd;
+
 /// Represents the OS's handle to the IO instance. In this case, it is the file
 /// descriptor.
 #[deriving(Show)]
@@ -24,6 +29,27 @@ impl Drop for IoDesc {
     }
 }
 
+impl IoDesc {
+    /// Returns the underlying fd without transferring ownership of it.
+    pub fn as_raw_fd(&self) -> Fd {
+        self.fd
+    }
+
+    /// Consumes `self`, returning the underlying fd without closing it.
+    pub fn into_raw_fd(self) -> Fd {
+        let fd = self.fd;
+        unsafe { mem::forget(self); }
+        fd
+    }
+
+    /// Wraps an externally created fd (e.g. received over `SCM_RIGHTS`, or
+    /// inherited from a parent process). The caller is responsible for
+    /// ensuring `fd` is a valid, open descriptor that isn't owned elsewhere.
+    pub unsafe fn from_raw_fd(fd: Fd) -> IoDesc {
+        IoDesc { fd: fd }
+    }
+}
+
 /*
  *
  * ===== Pipes =====
@@ -37,21 +63,47 @@ pub fn pipe() -> MioResult<(IoDesc, IoDesc)>{
     Ok((IoDesc { fd: rd }, IoDesc { fd: wr }))
 }
 
+pub fn shutdown(io: &IoDesc, how: Shutdown) -> MioResult<()> {
+    let how = match how {
+        Read  => nix::SHUT_RD,
+        Write => nix::SHUT_WR,
+        Both  => nix::SHUT_RDWR
+    };
+
+    nix::shutdown(io.fd, how)
+        .map_err(MioError::from_sys_error)
+}
+
+/// Duplicates the fd underlying `io`. The clone shares the same kernel file
+/// description (offset, status flags, etc.) but is an independent fd that
+/// can be closed and registered with the `Reactor` on its own.
+pub fn dup(io: &IoDesc) -> MioResult<IoDesc> {
+    Ok(IoDesc {
+        fd: try!(nix::fcntl(io.fd, nix::F_DUPFD_CLOEXEC(0))
+                    .map_err(MioError::from_sys_error))
+    })
+}
+
 /*
  *
  * ===== Sockets =====
  *
  */
 
-pub fn socket(af: AddressFamily) -> MioResult<IoDesc> {
+pub fn socket(af: AddressFamily, ty: SockType) -> MioResult<IoDesc> {
     let family = match af {
         Inet  => nix::AF_INET,
         Inet6 => nix::AF_INET6,
         _     => unimplemented!()
     };
 
+    let socket_type = match ty {
+        Stream => nix::SOCK_STREAM,
+        Dgram  => nix::SOCK_DGRAM
+    };
+
     Ok(IoDesc {
-        fd: try!(nix::socket(family, nix::SOCK_STREAM, nix::SOCK_NONBLOCK | nix::SOCK_CLOEXEC)
+        fd: try!(nix::socket(family, socket_type, nix::SOCK_NONBLOCK | nix::SOCK_CLOEXEC)
                     .map_err(MioError::from_sys_error))
     })
 }
@@ -113,6 +165,94 @@ pub fn write<B: Iobuf>(io: &IoDesc, src: &mut B) -> MioResult<()> {
     Ok(())
 }
 
+/// Reads a single datagram into the buffer, returning the address it came
+/// from. The buffer is advanced by the amount read.
+#[inline]
+pub fn recvfrom(io: &IoDesc, dst: &mut RWIobuf) -> MioResult<SockAddr> {
+    let (num_read, from) = try!(unsafe {
+        nix::recvfrom(io.fd, dst.as_mut_slice()).map_err(MioError::from_sys_error)
+    });
+
+    unsafe {
+        debug_assert!(num_read <= dst.len());
+        dst.unsafe_advance(num_read);
+    }
+
+    Ok(to_sockaddr(&from))
+}
+
+/// Sends a single datagram to the given address. The buffer is advanced by
+/// the amount written.
+#[inline]
+pub fn sendto<B: Iobuf>(io: &IoDesc, src: &mut B, addr: &SockAddr) -> MioResult<()> {
+    let num_written = try!(nix::sendto(io.fd, src.as_slice(), &from_sockaddr(addr))
+                               .map_err(MioError::from_sys_error));
+
+    unsafe {
+        debug_assert!(num_written <= src.len());
+        src.unsafe_advance(num_written);
+    }
+
+    Ok(())
+}
+
+/// Reads into as many of `dst` as the kernel will fill in a single `readv(2)`
+/// call, advancing each buffer's cursor in turn. Earlier buffers are fully
+/// consumed before the buffer the byte count lands in is partially advanced.
+#[inline]
+pub fn readv(io: &IoDesc, dst: &mut [RWIobuf]) -> MioResult<()> {
+    let mut iovs: Vec<nix::iovec> = dst.iter_mut()
+        .map(|buf| unsafe {
+            let s = buf.as_mut_slice();
+            nix::iovec { iov_base: s.as_mut_ptr(), iov_len: s.len() as u64 }
+        })
+        .collect();
+
+    let num_read = try!(unsafe {
+        nix::readv(io.fd, iovs.as_mut_slice()).map_err(MioError::from_sys_error)
+    });
+
+    let mut remaining = num_read;
+    for buf in dst.iter_mut() {
+        let advance = ::std::cmp::min(remaining, buf.len());
+
+        unsafe { buf.unsafe_advance(advance); }
+        remaining -= advance;
+    }
+
+    if num_read == 0 {
+        return Err(MioError::eof());
+    }
+
+    Ok(())
+}
+
+/// Writes as much of `src` as the kernel will accept in a single `writev(2)`
+/// call, advancing each buffer's cursor in turn.
+#[inline]
+pub fn writev<B: Iobuf>(io: &IoDesc, src: &mut [B]) -> MioResult<()> {
+    let mut iovs: Vec<nix::iovec> = src.iter_mut()
+        .map(|buf| unsafe {
+            let s = buf.as_slice();
+            nix::iovec { iov_base: s.as_ptr() as *mut u8, iov_len: s.len() as u64 }
+        })
+        .collect();
+
+    let num_written = try!(unsafe {
+        nix::writev(io.fd, iovs.as_mut_slice()).map_err(MioError::from_sys_error)
+    });
+
+    let mut remaining = num_written;
+    for buf in src.iter_mut() {
+        let advance = ::std::cmp::min(remaining, buf.len());
+
+        unsafe { buf.unsafe_advance(advance); }
+        remaining -= advance;
+    }
+
+    Ok(())
+}
+
 // ===== Socket options =====
 
 pub fn reuseaddr(_io: &IoDesc) -> MioResult<uint> {
@@ -164,7 +304,51 @@ fn from_sockaddr(addr: &SockAddr) -> nix::SockAddr {
 
                     nix::SockIpV4(addr)
                 }
-                _ => unimplemented!()
+                IpV6Addr(a, b, c, d, e, f, g, h) => {
+                    let mut addr: nix::sockaddr_in6 = unsafe { mem::zeroed() };
+
+                    addr.sin6_family = nix::AF_INET6 as nix::sa_family_t;
+                    addr.sin6_port = port.to_be();
+                    addr.sin6_addr = ip6_to_in6addr(a, b, c, d, e, f, g, h);
+
+                    nix::SockIpV6(addr)
+                }
+            }
+        }
+        _ => unimplemented!()
+    }
+}
+
+fn to_sockaddr(addr: &nix::SockAddr) -> SockAddr {
+    match *addr {
+        nix::SockIpV4(ref sin) => {
+            let ip = Int::from_be(sin.sin_addr.s_addr) as u32;
+
+            InetAddr(
+                IpV4Addr(
+                    ((ip >> 24) & 0xff) as u8,
+                    ((ip >> 16) & 0xff) as u8,
+                    ((ip >>  8) & 0xff) as u8,
+                    ((ip >>  0) & 0xff) as u8),
+                Int::from_be(sin.sin_port))
+        }
+        nix::SockIpV6(ref sin6) => {
+            let seg = in6addr_to_segments(&sin6.sin6_addr);
+
+            // IPv4-mapped IPv6 address (::ffff:a.b.c.d) -- surface it as a
+            // plain v4 address so dual-stack listeners behave the same way
+            // regardless of which family accepted the connection.
+            if seg[0] == 0 && seg[1] == 0 && seg[2] == 0 &&
+               seg[3] == 0 && seg[4] == 0 && seg[5] == 0xffff {
+                InetAddr(
+                    IpV4Addr(
+                        (seg[6] >> 8) as u8, (seg[6] & 0xff) as u8,
+                        (seg[7] >> 8) as u8, (seg[7] & 0xff) as u8),
+                    Int::from_be(sin6.sin6_port))
+            } else {
+                InetAddr(
+                    IpV6Addr(seg[0], seg[1], seg[2], seg[3], seg[4], seg[5], seg[6], seg[7]),
+                    Int::from_be(sin6.sin6_port))
             }
         }
         _ => unimplemented!()
@@ -181,3 +365,114 @@ fn ip4_to_inaddr(a: u8, b: u8, c: u8, d: u8) -> nix::in_addr {
         s_addr: Int::from_be(ip)
     }
 }
+
+fn ip6_to_in6addr(a: u16, b: u16, c: u16, d: u16, e: u16, f: u16, g: u16, h: u16) -> nix::in6_addr {
+    let segs = [a, b, c, d, e, f, g, h];
+    let mut bytes = [0u8, ..16];
+
+    for (i, seg) in segs.iter().enumerate() {
+        bytes[i * 2]     = (*seg >> 8) as u8;
+        bytes[i * 2 + 1] = (*seg & 0xff) as u8;
+    }
+
+    nix::in6_addr { s6_addr: bytes }
+}
+
+fn in6addr_to_segments(addr: &nix::in6_addr) -> [u16, ..8] {
+    let b = addr.s6_addr;
+    let mut segs = [0u16, ..8];
+
+    for i in range(0u, 8) {
+        segs[i] = (b[i * 2] as u16 << 8) | (b[i * 2 + 1] as u16);
+    }
+
+    segs
+}
+
+#[cfg(test)]
+mod test {
+    use iobuf::{Iobuf, ROIobuf, RWIobuf};
+    use socket::{InetAddr, IpV4Addr, IpV6Addr};
+    use super::{from_sockaddr, to_sockaddr, pipe, dup, read, write, readv};
+
+    #[test]
+    pub fn test_ipv4_sockaddr_roundtrip() {
+        let addr = InetAddr(IpV4Addr(127, 0, 0, 1), 4567);
+
+        match to_sockaddr(&from_sockaddr(&addr)) {
+            InetAddr(IpV4Addr(a, b, c, d), port) => {
+                assert_eq!((a, b, c, d), (127, 0, 0, 1));
+                assert_eq!(port, 4567);
+            }
+            _ => fail!("expected an InetAddr/IpV4Addr back")
+        }
+    }
+
+    #[test]
+    pub fn test_ipv6_sockaddr_roundtrip() {
+        let addr = InetAddr(
+            IpV6Addr(0x2001, 0x0db8, 0, 0, 0, 0, 0, 1),
+            4567);
+
+        match to_sockaddr(&from_sockaddr(&addr)) {
+            InetAddr(IpV6Addr(a, b, c, d, e, f, g, h), port) => {
+                assert_eq!((a, b, c, d, e, f, g, h), (0x2001, 0x0db8, 0, 0, 0, 0, 0, 1));
+                assert_eq!(port, 4567);
+            }
+            _ => fail!("expected an InetAddr/IpV6Addr back")
+        }
+    }
+
+    #[test]
+    pub fn test_ipv4_mapped_ipv6_sockaddr_roundtrip() {
+        // ::ffff:192.168.1.2
+        let addr = InetAddr(
+            IpV6Addr(0, 0, 0, 0, 0, 0xffff, 0xc0a8, 0x0102),
+            4567);
+
+        match to_sockaddr(&from_sockaddr(&addr)) {
+            InetAddr(IpV4Addr(a, b, c, d), port) => {
+                assert_eq!((a, b, c, d), (192, 168, 1, 2));
+                assert_eq!(port, 4567);
+            }
+            _ => fail!("expected the mapped address to surface as an IpV4Addr")
+        }
+    }
+
+    #[test]
+    pub fn test_readv_advances_each_buffer_in_turn() {
+        let (rd, wr) = pipe().unwrap();
+
+        let mut src = ROIobuf::from_str("0123456");
+        write(&wr, &mut src).unwrap();
+
+        let mut bufs = [RWIobuf::new(3), RWIobuf::new(3), RWIobuf::new(4)];
+        readv(&rd, bufs.as_mut_slice()).unwrap();
+
+        // 7 bytes landed across 3/3/4 capacity buffers: the first two are
+        // fully consumed, and the third is only partially advanced.
+        assert_eq!(bufs[0].len(), 0);
+        assert_eq!(bufs[1].len(), 0);
+        assert_eq!(bufs[2].len(), 3);
+    }
+
+    #[test]
+    pub fn test_dup_produces_an_independent_fd() {
+        let (rd, wr) = pipe().unwrap();
+        let wr_clone = dup(&wr).unwrap();
+
+        assert!(wr_clone.fd != wr.fd);
+
+        // Dropping the original must not close the clone's fd -- they share
+        // the kernel file description but are independent descriptors.
+        drop(wr);
+
+        let mut src = ROIobuf::from_str("hi");
+        write(&wr_clone, &mut src).unwrap();
+
+        let mut dst = RWIobuf::new(2);
+        read(&rd, &mut dst).unwrap();
+
+        assert_eq!(dst.len(), 0);
+    }
+}
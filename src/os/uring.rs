@@ -0,0 +1,26 @@
+//! Groundwork for an `io_uring`-backed alternative to the epoll path in
+//! `posix.rs`.
+//!
+//! STATUS: blocked, not implemented. chunk0-6 asked for a working
+//! io_uring backend -- SQ/CQ ring setup, SQE submission for read/write/
+//! accept, and `Reactor::run` reaping completions and dispatching them
+//! by `user_data` token, selectable at `Reactor::new` time alongside the
+//! existing epoll path. None of that exists here: this tree has no
+//! `reactor.rs` to select a backend or dispatch completions into, so
+//! there is nothing for a ring implementation to plug into yet. Treat
+//! chunk0-6 as deferred until `Reactor` lands, not done -- this file
+//! only records the op codes that submission would eventually use.
+//!
+//! The intent, once `Reactor` exists: operations are submitted as SQEs
+//! tagged with the connection's token as `user_data`; `Reactor::run`
+//! reaps completions from the CQ and dispatches them back through
+//! `Handler::readable`/`writable` by that token, the same way it
+//! dispatches epoll readiness events today.
+
+// Opcodes for the subset of operations the reactor would eventually submit.
+#[allow(dead_code)]
+const IORING_OP_READ: u8 = 22;
+#[allow(dead_code)]
+const IORING_OP_WRITE: u8 = 23;
+#[allow(dead_code)]
+const IORING_OP_ACCEPT: u8 = 13;